@@ -0,0 +1,136 @@
+use crate::client::RedisClientInner;
+use crate::error::{RedisError, RedisErrorKind};
+use bytes::BytesMut;
+use redis_protocol::types::Frame as ProtocolFrame;
+use redis_protocol::{decode::decode_bytes, encode::encode_bytes};
+use std::sync::Arc;
+use tokio_util::codec::{Decoder, Encoder};
+
+/// Default size of the read window requested per poll while a frame is still incomplete.
+pub const DEFAULT_READ_BUFFER_WINDOW_BYTES: usize = 8 * 1024;
+/// Default hard cap on how large a single connection's read buffer is allowed to grow before
+/// the connection is considered wedged and closed with a protocol error.
+pub const DEFAULT_READ_BUFFER_MAX_BYTES: usize = 8 * 1024 * 1024;
+
+pub struct RedisCodec {
+  pub server: String,
+  read_buffer_window: usize,
+  read_buffer_max: usize,
+}
+
+impl RedisCodec {
+  pub fn new(inner: &Arc<RedisClientInner>, server: String) -> Self {
+    let read_buffer_window = inner
+      .config
+      .read()
+      .read_buffer_window()
+      .unwrap_or(DEFAULT_READ_BUFFER_WINDOW_BYTES);
+    let read_buffer_max = inner
+      .config
+      .read()
+      .read_buffer_max()
+      .unwrap_or(DEFAULT_READ_BUFFER_MAX_BYTES);
+
+    RedisCodec {
+      server,
+      read_buffer_window,
+      read_buffer_max,
+    }
+  }
+}
+
+impl Decoder for RedisCodec {
+  type Item = ProtocolFrame;
+  type Error = RedisError;
+
+  fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+    if src.len() > self.read_buffer_max {
+      return Err(RedisError::new(
+        RedisErrorKind::ProtocolError,
+        format!(
+          "Read buffer on {} exceeded the {}-byte limit.",
+          self.server, self.read_buffer_max
+        ),
+      ));
+    }
+
+    match decode_bytes(src)? {
+      Some((frame, consumed)) => {
+        let _ = src.split_to(consumed);
+        Ok(Some(frame))
+      }
+      // make sure at least `read_buffer_window` bytes are free for the next read. this is a lower
+      // bound only: spare capacity above the window is left as is, `reserve` may allocate more
+      // than requested, and the buffer's total size is limited only by `read_buffer_max`.
+      None => {
+        let available = src.capacity() - src.len();
+        if available < self.read_buffer_window {
+          src.reserve(self.read_buffer_window - available);
+        }
+        Ok(None)
+      }
+    }
+  }
+}
+
+impl Encoder<ProtocolFrame> for RedisCodec {
+  type Error = RedisError;
+
+  fn encode(&mut self, frame: ProtocolFrame, dst: &mut BytesMut) -> Result<(), Self::Error> {
+    encode_bytes(dst, &frame)?;
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn test_codec(window: usize, max: usize) -> RedisCodec {
+    RedisCodec {
+      server: "test".into(),
+      read_buffer_window: window,
+      read_buffer_max: max,
+    }
+  }
+
+  #[test]
+  fn should_decode_a_complete_frame() {
+    let mut codec = test_codec(DEFAULT_READ_BUFFER_WINDOW_BYTES, DEFAULT_READ_BUFFER_MAX_BYTES);
+    let mut buf = BytesMut::new();
+    encode_bytes(&mut buf, &ProtocolFrame::SimpleString("OK".into())).unwrap();
+
+    let frame = codec.decode(&mut buf).unwrap();
+    assert_eq!(frame, Some(ProtocolFrame::SimpleString("OK".into())));
+    assert!(buf.is_empty());
+  }
+
+  #[test]
+  fn should_buffer_a_partial_frame() {
+    let mut codec = test_codec(DEFAULT_READ_BUFFER_WINDOW_BYTES, DEFAULT_READ_BUFFER_MAX_BYTES);
+    let mut full = BytesMut::new();
+    encode_bytes(&mut full, &ProtocolFrame::SimpleString("OK".into())).unwrap();
+    let mut partial = full.split_to(full.len() - 1);
+
+    let frame = codec.decode(&mut partial).unwrap();
+    assert_eq!(frame, None);
+    assert!(!partial.is_empty());
+  }
+
+  #[test]
+  fn should_grow_the_read_window_on_a_partial_frame() {
+    let mut codec = test_codec(64, DEFAULT_READ_BUFFER_MAX_BYTES);
+    let mut buf = BytesMut::from(&b"*1\r\n"[..]);
+
+    let _ = codec.decode(&mut buf).unwrap();
+    assert!(buf.capacity() - buf.len() >= 64);
+  }
+
+  #[test]
+  fn should_error_above_the_read_buffer_max() {
+    let mut codec = test_codec(DEFAULT_READ_BUFFER_WINDOW_BYTES, 4);
+    let mut buf = BytesMut::from(&b"abcdefgh"[..]);
+
+    assert!(codec.decode(&mut buf).is_err());
+  }
+}